@@ -0,0 +1,97 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::Deserialize;
+
+use crate::repo::RepositorySetup;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    pub name: String,
+    pub include_for: Option<Vec<String>>,
+    pub exclude_for: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub fixture_type: FixtureType,
+}
+
+impl Fixture {
+    /// Returns true if this fixture should be skipped on the current OS.
+    pub fn skip(&self) -> bool {
+        let os = std::env::consts::OS;
+
+        if let Some(include_for) = &self.include_for {
+            return !include_for.iter().any(|o| o == os);
+        }
+
+        if let Some(exclude_for) = &self.exclude_for {
+            return exclude_for.iter().any(|o| o == os);
+        }
+
+        false
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FixtureType {
+    Files(FilesSetup),
+    Repository(RepositorySetup),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FilesSetup {
+    pub files: Vec<File>,
+    #[serde(default)]
+    pub root: bool,
+    #[serde(default)]
+    pub secrets: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct File {
+    pub src: FileDefinition,
+    pub dest: FileDefinition,
+    #[serde(default)]
+    pub raw: bool,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default)]
+    pub method: LinkMethod,
+    /// Octal permission bits to apply after writing, e.g. `"0600"`. When
+    /// unset, an existing `dest`'s permissions are left as they were.
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+/// How a fixture's rendered content ends up at `dest`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkMethod {
+    /// Write the rendered content to `dest`.
+    #[default]
+    Copy,
+    /// Symlink `dest` to `src` instead of copying, so edits at `dest` flow
+    /// straight back into the source repo.
+    Symlink,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum FileDefinition {
+    Single(PathBuf),
+    Multiple(HashMap<String, PathBuf>),
+}
+
+impl FileDefinition {
+    /// Resolves this definition to a concrete path for the current OS,
+    /// returning `None` if a `Multiple` variant has no entry for it.
+    pub fn resolve(&self) -> Option<PathBuf> {
+        match self {
+            FileDefinition::Single(path) => Some(path.clone()),
+            FileDefinition::Multiple(paths) => paths.get(std::env::consts::OS).cloned(),
+        }
+    }
+}
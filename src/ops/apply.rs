@@ -1,20 +1,35 @@
 use std::{
     io::Write,
+    os::unix::fs::PermissionsExt,
     path::{Path, PathBuf},
 };
 
-use log::error;
+use log::{error, warn};
+use nix::unistd::{Group, User};
 use termcolor::{ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::{
-    fixture::{Fixture, FixtureType},
-    repo, template,
+    fixture::{File, FilesSetup, Fixture, FixtureType, LinkMethod},
+    privilege, repo, template,
 };
 
+/// `revert_generation` scopes a specific backup generation id to the single
+/// destination path it was listed for - generation ids are per-file
+/// timestamps, so applying one fixture-wide would misfire a `NotFound` for
+/// every other file in the revert. Every other destination restores its
+/// newest generation as usual.
+///
+/// This is the restore-by-version entry point: a `list` command pairs
+/// `list_generations` (below) with a destination path to show the user their
+/// choices, and a `restore` command re-runs this fixture list with
+/// `revert: true` and `revert_generation` set to the id they picked. No
+/// command layer wires that up yet in this crate - it's library plumbing for
+/// one to call into.
 pub fn apply_fixtures(
     fixtures: Vec<Fixture>,
     revert: bool,
     no_backup: bool,
+    revert_generation: Option<(PathBuf, u64)>,
 ) -> std::io::Result<()> {
     let backup_dir = dirs::state_dir().unwrap().join("spaceconf");
     let mut stdout = StandardStream::stdout(ColorChoice::Auto);
@@ -25,7 +40,7 @@ pub fn apply_fixtures(
 
         match fixture.fixture_type {
             FixtureType::Files(setup) => {
-                for file in setup.files {
+                for file in &setup.files {
                     let Some(src) = file.src.resolve() else {
                         continue;
                     };
@@ -33,59 +48,39 @@ pub fn apply_fixtures(
                         continue;
                     };
 
-                    stdout.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::White)))?;
-                    if revert {
-                        if let Err(e) = restore_file(&backup_dir, &dest, setup.root) {
-                            eprintln!("Failed to restore {:?}: {}", dest, e);
-                            return Err(e);
-                        }
-                    } else {
-                        let output = if file.raw {
-                            std::fs::read_to_string(&src).inspect_err(|_| {
-                                error!("failed to read source file: {}", &src.to_string_lossy())
-                            })?
-                        } else {
-                            let input = std::fs::read_to_string(&src).inspect_err(|_| {
-                                error!("failed to read source file: {}", &src.to_string_lossy())
-                            })?;
-                            template::render(&input, &setup.secrets).unwrap()
-                        };
-
-                        if check_content(&output, &dest) {
-                            stdout.set_color(
-                                ColorSpec::new().set_fg(Some(termcolor::Color::Green)),
-                            )?;
-                            writeln!(&mut stdout, "{} is up to date", dest.to_string_lossy())
-                                .unwrap();
-                            stdout.set_color(
-                                ColorSpec::new().set_fg(Some(termcolor::Color::White)),
-                            )?;
-                            continue;
-                        }
-
-                        if !no_backup {
-                            if !backup_dir.exists() {
-                                std::fs::create_dir_all(&backup_dir).inspect_err(|_| {
-                                    error!(
-                                        "failed to create parent directory(s): {}",
-                                        &backup_dir.to_string_lossy()
-                                    )
-                                })?;
+                    for (src, dest) in expand_src(&src, &dest) {
+                        // Root fixtures leave their parent directory to
+                        // `write_root`'s own escalated `mkdir -p` - creating
+                        // it here unprivileged would fail with
+                        // `PermissionDenied` before `write_root` ever runs.
+                        if !setup.root {
+                            if let Some(parent) = dest.parent() {
+                                if !parent.exists() {
+                                    std::fs::create_dir_all(parent).inspect_err(|_| {
+                                        error!(
+                                            "failed to create parent directory(s): {}",
+                                            &parent.to_string_lossy()
+                                        )
+                                    })?;
+                                }
                             }
-                            backup_file(&backup_dir, &dest);
                         }
 
-                        if setup.root {
-                            write_root(&dest, &output)?;
-                        } else {
-                            std::fs::write(&dest, output).inspect_err(|_| {
-                                error!(
-                                    "failed to read destination file: {}",
-                                    &dest.to_string_lossy()
-                                )
-                            })?;
-                        }
-                        println!("Applying {:?}", dest);
+                        let generation = revert_generation
+                            .as_ref()
+                            .and_then(|(path, generation)| (path == &dest).then_some(*generation));
+
+                        apply_file(
+                            file,
+                            &src,
+                            &dest,
+                            &setup,
+                            revert,
+                            no_backup,
+                            generation,
+                            &backup_dir,
+                            &mut stdout,
+                        )?;
                     }
                 }
             }
@@ -98,7 +93,192 @@ pub fn apply_fixtures(
     Ok(())
 }
 
-fn check_content(content: &str, output: &PathBuf) -> bool {
+#[allow(clippy::too_many_arguments)]
+fn apply_file(
+    file: &File,
+    src: &Path,
+    dest: &Path,
+    setup: &FilesSetup,
+    revert: bool,
+    no_backup: bool,
+    revert_generation: Option<u64>,
+    backup_dir: &Path,
+    stdout: &mut StandardStream,
+) -> std::io::Result<()> {
+    // Only validated outside of revert - a fixture that's invalid going
+    // forward must still be revertible, so `--revert` can clean up a symlink
+    // that was linked before `raw: true` was required for it.
+    if !revert && file.method == LinkMethod::Symlink && !file.raw {
+        error!(
+            "symlink fixtures cannot be templated, set raw: true for {}",
+            &dest.to_string_lossy()
+        );
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "symlink fixtures must be raw",
+        ));
+    }
+
+    stdout.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::White)))?;
+    if revert {
+        if let Err(e) = restore_file(backup_dir, dest, setup.root, revert_generation) {
+            eprintln!("Failed to restore {:?}: {}", dest, e);
+            return Err(e);
+        }
+    } else if file.method == LinkMethod::Symlink {
+        if check_symlink(src, dest) {
+            stdout.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Green)))?;
+            writeln!(stdout, "{} is up to date", dest.to_string_lossy()).unwrap();
+            stdout.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::White)))?;
+            return Ok(());
+        }
+
+        if !no_backup {
+            if !backup_dir.exists() {
+                std::fs::create_dir_all(backup_dir).inspect_err(|_| {
+                    error!(
+                        "failed to create parent directory(s): {}",
+                        &backup_dir.to_string_lossy()
+                    )
+                })?;
+            }
+            backup_file(backup_dir, dest);
+        }
+
+        link_file(src, dest)
+            .inspect_err(|_| error!("failed to link {}", &dest.to_string_lossy()))?;
+        println!("Linking {:?} -> {:?}", dest, src);
+    } else {
+        let output = if file.raw {
+            std::fs::read_to_string(src).inspect_err(|_| {
+                error!("failed to read source file: {}", &src.to_string_lossy())
+            })?
+        } else {
+            let input = std::fs::read_to_string(src).inspect_err(|_| {
+                error!("failed to read source file: {}", &src.to_string_lossy())
+            })?;
+            template::render(&input, &setup.secrets).unwrap()
+        };
+
+        if check_content(&output, dest) {
+            stdout.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::Green)))?;
+            writeln!(stdout, "{} is up to date", dest.to_string_lossy()).unwrap();
+            stdout.set_color(ColorSpec::new().set_fg(Some(termcolor::Color::White)))?;
+            return Ok(());
+        }
+
+        if !no_backup {
+            if !backup_dir.exists() {
+                std::fs::create_dir_all(backup_dir).inspect_err(|_| {
+                    error!(
+                        "failed to create parent directory(s): {}",
+                        &backup_dir.to_string_lossy()
+                    )
+                })?;
+            }
+            backup_file(backup_dir, dest);
+        }
+
+        let previous_mode = existing_mode(dest);
+
+        if setup.root {
+            write_root(dest, &output)?;
+        } else {
+            write_atomic(dest, &output).inspect_err(|_| {
+                error!(
+                    "failed to write destination file: {}",
+                    &dest.to_string_lossy()
+                )
+            })?;
+        }
+
+        apply_attributes(file, dest, setup.root, previous_mode).inspect_err(|_| {
+            error!(
+                "failed to apply mode/ownership to {}",
+                &dest.to_string_lossy()
+            )
+        })?;
+
+        println!("Applying {:?}", dest);
+    }
+
+    Ok(())
+}
+
+/// Expands a `src` containing wildmatch-style wildcards (`*`, `?`) into every
+/// matching file under its fixed-path prefix, pairing each with a `dest`
+/// that mirrors its path relative to that prefix. A `src` with no wildcards
+/// is returned unchanged as the only entry.
+fn expand_src(src: &Path, dest: &Path) -> Vec<(PathBuf, PathBuf)> {
+    let Some(prefix) = glob_prefix(src) else {
+        return vec![(src.to_path_buf(), dest.to_path_buf())];
+    };
+
+    let pattern = src
+        .strip_prefix(&prefix)
+        .unwrap()
+        .to_string_lossy()
+        .replace(std::path::MAIN_SEPARATOR, "/");
+    let matcher = wildmatch::WildMatch::new(&pattern);
+
+    let matches: Vec<(PathBuf, PathBuf)> = walkdir::WalkDir::new(&prefix)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let relative = entry.path().strip_prefix(&prefix).ok()?;
+            let relative_str = relative.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/");
+            if matcher.matches(&relative_str) {
+                Some((entry.path().to_path_buf(), dest.join(relative)))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if matches.is_empty() {
+        warn!(
+            "glob src {} matched no files under {}",
+            &src.to_string_lossy(),
+            &prefix.to_string_lossy()
+        );
+    }
+
+    matches
+}
+
+/// Returns the longest path prefix of `src` that contains no wildcard
+/// characters, or `None` if `src` has none.
+fn glob_prefix(src: &Path) -> Option<PathBuf> {
+    // Only the characters `wildmatch` actually treats as wildcards - it has
+    // no bracket-class support, so a literal `[` must stay in the fixed
+    // prefix rather than truncating it into an unmatchable pattern.
+    const WILDCARD_CHARS: [char; 2] = ['*', '?'];
+
+    if !src
+        .to_string_lossy()
+        .chars()
+        .any(|c| WILDCARD_CHARS.contains(&c))
+    {
+        return None;
+    }
+
+    let mut prefix = PathBuf::new();
+    for component in src.components() {
+        if component
+            .as_os_str()
+            .to_string_lossy()
+            .chars()
+            .any(|c| WILDCARD_CHARS.contains(&c))
+        {
+            break;
+        }
+        prefix.push(component);
+    }
+    Some(prefix)
+}
+
+fn check_content(content: &str, output: &Path) -> bool {
     if !output.exists() {
         return false;
     }
@@ -108,24 +288,341 @@ fn check_content(content: &str, output: &PathBuf) -> bool {
     content == existing_content
 }
 
-fn backup_file(backup_dir: &Path, file: &PathBuf) {
+/// Returns true if `dest` is already a symlink pointing at `src`.
+fn check_symlink(src: &Path, dest: &Path) -> bool {
+    match std::fs::read_link(dest) {
+        Ok(target) => target == src,
+        Err(_) => false,
+    }
+}
+
+/// Links `dest` to `src`, replacing (but not backing up - the caller is
+/// responsible for that) any existing file or symlink at `dest`.
+fn link_file(src: &Path, dest: &Path) -> std::io::Result<()> {
+    if dest.symlink_metadata().is_ok() {
+        std::fs::remove_file(dest)?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(src, dest)?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(src, dest)?;
+
+    Ok(())
+}
+
+/// Reads `dest`'s current permission bits, if it exists. Masked to the
+/// permission bits (`0o7777`) - `Permissions::mode()` also carries the file
+/// type (e.g. `S_IFREG`), which `chmod_root` would otherwise pass straight
+/// through to the `chmod` binary as an invalid mode.
+fn existing_mode(dest: &Path) -> Option<u32> {
+    std::fs::metadata(dest)
+        .ok()
+        .map(|metadata| metadata.permissions().mode() & 0o7777)
+}
+
+/// Applies `file`'s configured mode/owner/group to `dest`, falling back to
+/// `previous_mode` (the destination's permissions before this write) when no
+/// mode was configured, so applying a fixture never resets permissions that
+/// weren't asked to change.
+fn apply_attributes(
+    file: &File,
+    dest: &Path,
+    root: bool,
+    previous_mode: Option<u32>,
+) -> std::io::Result<()> {
+    let mode = match &file.mode {
+        Some(mode) => Some(u32::from_str_radix(mode, 8).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid mode: {}", mode),
+            )
+        })?),
+        None => previous_mode,
+    };
+
+    if root {
+        if mode.is_some() || file.owner.is_some() || file.group.is_some() {
+            let escalation = privilege::detect()?;
+            if let Some(mode) = mode {
+                chmod_root(escalation, dest, mode)?;
+            }
+            if file.owner.is_some() || file.group.is_some() {
+                chown_root(escalation, dest, file.owner.as_deref(), file.group.as_deref())?;
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(mode) = mode {
+        std::fs::set_permissions(dest, std::fs::Permissions::from_mode(mode))?;
+    }
+
+    if file.owner.is_some() || file.group.is_some() {
+        let uid = file.owner.as_deref().map(resolve_uid).transpose()?;
+        let gid = file.group.as_deref().map(resolve_gid).transpose()?;
+        nix::unistd::chown(dest, uid, gid)
+            .map_err(|errno| std::io::Error::from_raw_os_error(errno as i32))?;
+    }
+
+    Ok(())
+}
+
+/// Resolves `owner` to a uid, erroring (rather than silently skipping the
+/// chown) if the name doesn't exist - a configured owner that can't be
+/// applied must not pass as success.
+fn resolve_uid(owner: &str) -> std::io::Result<nix::unistd::Uid> {
+    User::from_name(owner)
+        .map_err(|errno| std::io::Error::from_raw_os_error(errno as i32))?
+        .map(|user| user.uid)
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no such user: {owner}"),
+            )
+        })
+}
+
+fn resolve_gid(group: &str) -> std::io::Result<nix::unistd::Gid> {
+    Group::from_name(group)
+        .map_err(|errno| std::io::Error::from_raw_os_error(errno as i32))?
+        .map(|group| group.gid)
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no such group: {group}"),
+            )
+        })
+}
+
+/// Runs an escalated command to completion, turning a non-zero exit (wrong
+/// sudo password, target not found, permission denied, ...) into an error
+/// instead of silently discarding it.
+fn run_escalated(mut command: std::process::Command, description: &str) -> std::io::Result<()> {
+    let status = command.status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("{description} failed: {status}"),
+        ))
+    }
+}
+
+fn chmod_root(
+    escalation: privilege::PrivilegeEscalation,
+    dest: &Path,
+    mode: u32,
+) -> std::io::Result<()> {
+    let mut command = escalation.command("chmod");
+    command.arg(format!("{:o}", mode)).arg(dest);
+    run_escalated(
+        command,
+        &format!("chmod {:o} {}", mode, dest.to_string_lossy()),
+    )
+}
+
+fn chown_root(
+    escalation: privilege::PrivilegeEscalation,
+    dest: &Path,
+    owner: Option<&str>,
+    group: Option<&str>,
+) -> std::io::Result<()> {
+    let spec = match (owner, group) {
+        (Some(owner), Some(group)) => format!("{owner}:{group}"),
+        (Some(owner), None) => owner.to_string(),
+        (None, Some(group)) => format!(":{group}"),
+        (None, None) => return Ok(()),
+    };
+
+    let mut command = escalation.command("chown");
+    command.arg(&spec).arg(dest);
+    run_escalated(
+        command,
+        &format!("chown {} {}", spec, dest.to_string_lossy()),
+    )
+}
+
+/// The standard Unix errno for a rename across filesystems.
+const EXDEV: i32 = 18;
+
+/// Writes `content` to `dest` using the write-temp-then-rename pattern, so a
+/// crash or full disk mid-write can never leave a truncated or mixed file at
+/// `dest` - it's always either the old content or the new content.
+fn write_atomic(dest: &Path, content: &str) -> std::io::Result<()> {
+    let dir = dest.parent().unwrap_or_else(|| Path::new("."));
+    let temp_file = dir.join(format!(".spaceconf-{}.tmp", uuid::Uuid::new_v4()));
+
+    {
+        let mut file = std::fs::File::create(&temp_file).inspect_err(|_| {
+            error!(
+                "failed to create temporary file: {}",
+                &temp_file.to_string_lossy()
+            )
+        })?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    match std::fs::rename(&temp_file, dest) {
+        Ok(()) => Ok(()),
+        Err(e) if e.raw_os_error() == Some(EXDEV) => {
+            let result = std::fs::copy(&temp_file, dest).map(|_| ());
+            std::fs::remove_file(&temp_file).ok();
+            result.inspect_err(|_| {
+                error!(
+                    "failed to copy temporary file across devices to {}",
+                    &dest.to_string_lossy()
+                )
+            })
+        }
+        Err(e) => {
+            std::fs::remove_file(&temp_file).ok();
+            Err(e)
+        }
+    }
+}
+
+/// One stored copy of a destination file, identified by the Unix timestamp
+/// (nanoseconds) it was taken at.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct BackupGeneration {
+    timestamp: u64,
+    original_path: PathBuf,
+    hash: u64,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct BackupManifest {
+    /// Oldest first.
+    generations: Vec<BackupGeneration>,
+}
+
+/// The per-destination directory holding that file's backup generations and
+/// manifest, e.g. `<backup_dir>/etc/ssh/sshd_config/`.
+fn backup_generations_dir(backup_dir: &Path, file: &Path) -> PathBuf {
+    backup_dir.join(file.strip_prefix("/").unwrap())
+}
+
+fn manifest_path(generations_dir: &Path) -> PathBuf {
+    generations_dir.join("manifest.json")
+}
+
+fn generation_filename(timestamp: u64) -> String {
+    format!("{timestamp}.bak")
+}
+
+fn load_manifest(generations_dir: &Path) -> BackupManifest {
+    std::fs::read_to_string(manifest_path(generations_dir))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(generations_dir: &Path, manifest: &BackupManifest) -> std::io::Result<()> {
+    let contents = serde_json::to_string_pretty(manifest)
+        .expect("BackupManifest is always serializable");
+    std::fs::write(manifest_path(generations_dir), contents)
+}
+
+fn hash_contents(content: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Lists the stored backup generations for `file`, oldest first - the other
+/// half of the restore-by-version plumbing described on `apply_fixtures`.
+pub fn list_generations(backup_dir: &Path, file: &Path) -> Vec<u64> {
+    load_manifest(&backup_generations_dir(backup_dir, file))
+        .generations
+        .iter()
+        .map(|generation| generation.timestamp)
+        .collect()
+}
+
+/// Stores a new backup generation of `file`, unless its content is
+/// byte-identical to the most recent stored generation.
+fn backup_file(backup_dir: &Path, file: &Path) {
     if !file.exists() {
         return;
     }
 
-    let backup_file = get_backup_filename(backup_dir, file);
-    std::fs::create_dir_all(backup_file.parent().unwrap()).unwrap();
-    std::fs::copy(file, backup_file).unwrap();
+    let generations_dir = backup_generations_dir(backup_dir, file);
+    std::fs::create_dir_all(&generations_dir).unwrap();
+
+    let content = std::fs::read(file).unwrap();
+    let hash = hash_contents(&content);
+
+    let mut manifest = load_manifest(&generations_dir);
+    if manifest
+        .generations
+        .last()
+        .is_some_and(|latest| latest.hash == hash)
+    {
+        return;
+    }
+
+    // Nanosecond resolution so generations created in quick succession still
+    // get distinct ids.
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64;
+
+    std::fs::write(generations_dir.join(generation_filename(timestamp)), &content).unwrap();
+
+    manifest.generations.push(BackupGeneration {
+        timestamp,
+        original_path: file.to_path_buf(),
+        hash,
+    });
+    save_manifest(&generations_dir, &manifest).unwrap();
 }
 
-fn restore_file(backup_dir: &Path, file: &PathBuf, root: bool) -> std::io::Result<()> {
-    let backup_file = get_backup_filename(backup_dir, file);
-    if !backup_file.exists() {
+/// Restores `file` from a backup generation. `generation` selects one by
+/// timestamp; `None` restores the newest.
+fn restore_file(
+    backup_dir: &Path,
+    file: &Path,
+    root: bool,
+    generation: Option<u64>,
+) -> std::io::Result<()> {
+    let generations_dir = backup_generations_dir(backup_dir, file);
+    let manifest = load_manifest(&generations_dir);
+
+    let selected = match generation {
+        Some(timestamp) => manifest
+            .generations
+            .iter()
+            .find(|generation| generation.timestamp == timestamp),
+        None => manifest.generations.last(),
+    };
+
+    let Some(selected) = selected else {
         eprintln!("Backup file does not exist for {:?}", file);
         return Err(std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "Backup file does not exist",
         ));
+    };
+
+    let backup_file = generations_dir.join(generation_filename(selected.timestamp));
+
+    // A symlinked `dest` must be removed (not written through) before the
+    // backed-up original is restored in its place.
+    if file.symlink_metadata().is_ok_and(|m| m.is_symlink()) {
+        if root {
+            let escalation = privilege::detect()?;
+            let mut rm = escalation.command("rm");
+            rm.arg(file);
+            run_escalated(rm, &format!("rm {}", file.to_string_lossy()))?;
+        } else {
+            std::fs::remove_file(file)?;
+        }
     }
 
     if root {
@@ -136,16 +633,8 @@ fn restore_file(backup_dir: &Path, file: &PathBuf, root: bool) -> std::io::Resul
     Ok(())
 }
 
-fn get_backup_filename(backup_dir: &Path, file: &Path) -> PathBuf {
-    backup_dir.join(file.strip_prefix("/").unwrap())
-}
-
-fn write_root(file: &PathBuf, content: &str) -> std::io::Result<()> {
-    #[cfg(not(target_os = "linux"))]
-    {
-        eprintln!("Root fixture is currently only supported on Linux");
-        std::process::exit(1);
-    }
+fn write_root(file: &Path, content: &str) -> std::io::Result<()> {
+    let escalation = privilege::detect()?;
 
     let temp_file = PathBuf::from(format!("/tmp/spaceconf-{}.tmp", uuid::Uuid::new_v4()));
     std::fs::write(&temp_file, content).inspect_err(|_| {
@@ -154,21 +643,40 @@ fn write_root(file: &PathBuf, content: &str) -> std::io::Result<()> {
             &temp_file.to_string_lossy()
         )
     })?;
-    if !file.parent().unwrap().exists() {
-        std::process::Command::new("sudo")
-            .arg("mkdir")
-            .arg("-p")
-            .arg(file.parent().unwrap())
-            .status()
-            .unwrap();
-    }
-    std::process::Command::new("sudo")
-        .arg("cp")
-        .arg(&temp_file)
-        .arg(file)
-        .status()
-        .unwrap();
-    Ok(())
+
+    let parent = file.parent().unwrap();
+    if !parent.exists() {
+        let mut mkdir = escalation.command("mkdir");
+        mkdir.arg("-p").arg(parent);
+        run_escalated(mkdir, &format!("mkdir -p {}", parent.to_string_lossy()))?;
+    }
+
+    // Stage the rendered content next to the destination, then move it into
+    // place with a single atomic rename, so `file` is never left half-written.
+    let staged_file = parent.join(format!(".spaceconf-{}.tmp", uuid::Uuid::new_v4()));
+    let mut cp = escalation.command("cp");
+    cp.arg(&temp_file).arg(&staged_file);
+    let cp_result = run_escalated(
+        cp,
+        &format!(
+            "cp {} {}",
+            temp_file.to_string_lossy(),
+            staged_file.to_string_lossy()
+        ),
+    );
+    std::fs::remove_file(&temp_file).ok();
+    cp_result?;
+
+    let mut mv = escalation.command("mv");
+    mv.arg(&staged_file).arg(file);
+    run_escalated(
+        mv,
+        &format!(
+            "mv {} {}",
+            staged_file.to_string_lossy(),
+            file.to_string_lossy()
+        ),
+    )
 }
 
 #[cfg(test)]
@@ -201,13 +709,17 @@ mod tests {
                     dest: FileDefinition::Single(dest_file.clone()),
                     raw: true,
                     optional: false,
+                    method: fixture::LinkMethod::Copy,
+                    mode: None,
+                    owner: None,
+                    group: None,
                 }],
                 root: false,
                 secrets: Default::default(),
             }),
         };
 
-        apply_fixtures(vec![fixture], false, true).unwrap();
+        apply_fixtures(vec![fixture], false, true, None).unwrap();
 
         assert!(dest_file.exists());
 
@@ -216,6 +728,45 @@ mod tests {
         assert_eq!(dest_content, file_content);
     }
 
+    #[test]
+    fn test_apply_symlink_fixture() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+
+        let file_content = "Hello, World!";
+
+        let source_file = test_dir.path().join("source.conf");
+        let dest_file = test_dir.path().join("dest.conf");
+
+        std::fs::write(&source_file, file_content).unwrap();
+
+        assert!(!dest_file.exists());
+
+        let fixture = Fixture {
+            name: "test-fixture".into(),
+            include_for: None,
+            exclude_for: None,
+            fixture_type: FixtureType::Files(fixture::FilesSetup {
+                files: vec![fixture::File {
+                    src: FileDefinition::Single(source_file.clone()),
+                    dest: FileDefinition::Single(dest_file.clone()),
+                    raw: true,
+                    optional: false,
+                    method: fixture::LinkMethod::Symlink,
+                    mode: None,
+                    owner: None,
+                    group: None,
+                }],
+                root: false,
+                secrets: Default::default(),
+            }),
+        };
+
+        apply_fixtures(vec![fixture], false, true, None).unwrap();
+
+        assert_eq!(std::fs::read_link(&dest_file).unwrap(), source_file);
+        assert_eq!(std::fs::read_to_string(&dest_file).unwrap(), file_content);
+    }
+
     #[test]
     fn test_apply_excluded_fixture() {
         let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
@@ -239,13 +790,17 @@ mod tests {
                     dest: FileDefinition::Single(dest_file.clone()),
                     raw: true,
                     optional: false,
+                    method: fixture::LinkMethod::Copy,
+                    mode: None,
+                    owner: None,
+                    group: None,
                 }],
                 root: false,
                 secrets: Default::default(),
             }),
         };
 
-        apply_fixtures(vec![fixture], false, true).unwrap();
+        apply_fixtures(vec![fixture], false, true, None).unwrap();
 
         assert!(!dest_file.exists());
     }
@@ -276,13 +831,17 @@ mod tests {
                     dest: FileDefinition::Single(dest_file.clone()),
                     raw: true,
                     optional: true,
+                    method: fixture::LinkMethod::Copy,
+                    mode: None,
+                    owner: None,
+                    group: None,
                 }],
                 root: false,
                 secrets: Default::default(),
             }),
         };
 
-        apply_fixtures(vec![fixture], false, true).unwrap();
+        apply_fixtures(vec![fixture], false, true, None).unwrap();
 
         assert!(!dest_file.exists());
     }
@@ -313,17 +872,151 @@ mod tests {
                     )])),
                     raw: true,
                     optional: true,
+                    method: fixture::LinkMethod::Copy,
+                    mode: None,
+                    owner: None,
+                    group: None,
                 }],
                 root: false,
                 secrets: Default::default(),
             }),
         };
 
-        apply_fixtures(vec![fixture], false, true).unwrap();
+        apply_fixtures(vec![fixture], false, true, None).unwrap();
 
         assert!(!dest_file.exists());
     }
 
+    #[test]
+    fn test_write_atomic_creates_file() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let dest_file = test_dir.path().join("dest.conf");
+
+        write_atomic(&dest_file, "Hello, World!").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&dest_file).unwrap(),
+            "Hello, World!"
+        );
+    }
+
+    #[test]
+    fn test_write_atomic_overwrites_existing_file() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let dest_file = test_dir.path().join("dest.conf");
+
+        std::fs::write(&dest_file, "Old content").unwrap();
+
+        write_atomic(&dest_file, "New content").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&dest_file).unwrap(), "New content");
+    }
+
+    #[test]
+    fn test_apply_glob_fixture() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+
+        let source_dir = test_dir.path().join("nvim");
+        std::fs::create_dir_all(source_dir.join("lua/plugins")).unwrap();
+        std::fs::write(source_dir.join("init.lua"), "-- init").unwrap();
+        std::fs::write(source_dir.join("lua/plugins/lsp.lua"), "-- lsp").unwrap();
+        std::fs::write(source_dir.join("README.md"), "not lua").unwrap();
+
+        let dest_dir = test_dir.path().join("dest");
+
+        let fixture = Fixture {
+            name: "test-fixture".into(),
+            include_for: None,
+            exclude_for: None,
+            fixture_type: FixtureType::Files(fixture::FilesSetup {
+                files: vec![fixture::File {
+                    src: FileDefinition::Single(source_dir.join("**/*.lua")),
+                    dest: FileDefinition::Single(dest_dir.clone()),
+                    raw: true,
+                    optional: false,
+                    method: fixture::LinkMethod::Copy,
+                    mode: None,
+                    owner: None,
+                    group: None,
+                }],
+                root: false,
+                secrets: Default::default(),
+            }),
+        };
+
+        apply_fixtures(vec![fixture], false, true, None).unwrap();
+
+        assert!(dest_dir.join("lua/plugins/lsp.lua").exists());
+        assert!(!dest_dir.join("README.md").exists());
+        assert!(!dest_dir.join("init.lua").exists());
+    }
+
+    #[test]
+    fn test_apply_files_fixture_with_mode() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+
+        let file_content = "Hello, World!";
+
+        let source_file = test_dir.path().join("source.conf");
+        let dest_file = test_dir.path().join("dest.conf");
+
+        std::fs::write(&source_file, file_content).unwrap();
+
+        let fixture = Fixture {
+            name: "test-fixture".into(),
+            include_for: None,
+            exclude_for: None,
+            fixture_type: FixtureType::Files(fixture::FilesSetup {
+                files: vec![fixture::File {
+                    src: FileDefinition::Single(source_file.clone()),
+                    dest: FileDefinition::Single(dest_file.clone()),
+                    raw: true,
+                    optional: false,
+                    method: fixture::LinkMethod::Copy,
+                    mode: Some("0600".into()),
+                    owner: None,
+                    group: None,
+                }],
+                root: false,
+                secrets: Default::default(),
+            }),
+        };
+
+        apply_fixtures(vec![fixture], false, true, None).unwrap();
+
+        let metadata = std::fs::metadata(&dest_file).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_apply_attributes_preserves_existing_mode_when_unset() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let dest_file = test_dir.path().join("dest.conf");
+
+        std::fs::write(&dest_file, "Old content").unwrap();
+        std::fs::set_permissions(&dest_file, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+        let previous_mode = existing_mode(&dest_file);
+
+        write_atomic(&dest_file, "New content").unwrap();
+
+        let file = fixture::File {
+            src: FileDefinition::Single(dest_file.clone()),
+            dest: FileDefinition::Single(dest_file.clone()),
+            raw: true,
+            optional: false,
+            method: fixture::LinkMethod::Copy,
+            mode: None,
+            owner: None,
+            group: None,
+        };
+
+        apply_attributes(&file, &dest_file, false, previous_mode).unwrap();
+
+        let metadata = std::fs::metadata(&dest_file).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o640);
+    }
+
     #[test]
     fn test_backup_file() {
         let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
@@ -334,41 +1027,84 @@ mod tests {
         let file = test_dir.path().join("file.txt");
         std::fs::write(&file, "Hello, World!").unwrap();
 
-        let backup_filename = backup_dir.join(file.strip_prefix("/").unwrap());
+        assert!(list_generations(&backup_dir, &file).is_empty());
+
+        backup_file(&backup_dir, &file);
+
+        assert_eq!(list_generations(&backup_dir, &file).len(), 1);
+    }
+
+    #[test]
+    fn test_backup_file_skips_unchanged_content() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+
+        let backup_dir = test_dir.path().join("backup");
+        std::fs::create_dir(&backup_dir).unwrap();
+
+        let file = test_dir.path().join("file.txt");
+        std::fs::write(&file, "Hello, World!").unwrap();
+
+        backup_file(&backup_dir, &file);
+        backup_file(&backup_dir, &file);
+
+        assert_eq!(list_generations(&backup_dir, &file).len(), 1);
+    }
 
-        assert!(!backup_filename.exists());
+    #[test]
+    fn test_backup_file_keeps_prior_generations() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
 
+        let backup_dir = test_dir.path().join("backup");
+        std::fs::create_dir(&backup_dir).unwrap();
+
+        let file = test_dir.path().join("file.txt");
+        std::fs::write(&file, "Hello, World!").unwrap();
+        backup_file(&backup_dir, &file);
+
+        std::fs::write(&file, "Hello again, World!").unwrap();
         backup_file(&backup_dir, &file);
 
-        assert!(backup_filename.exists());
+        assert_eq!(list_generations(&backup_dir, &file).len(), 2);
     }
 
     #[test]
-    fn test_restore_file() {
+    fn test_restore_file_defaults_to_newest_generation() {
         let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
 
         let backup_dir = test_dir.path().join("backup");
         std::fs::create_dir(&backup_dir).unwrap();
 
         let file = test_dir.path().join("file.txt");
-        std::fs::write(file.clone(), "Hello, World!").unwrap();
+        std::fs::write(&file, "Hello, World!").unwrap();
+        backup_file(&backup_dir, &file);
 
-        let backup_filename = backup_dir.join(file.strip_prefix("/").unwrap());
-        std::fs::create_dir_all(backup_filename.parent().unwrap()).unwrap();
-        std::fs::write(backup_filename, "Hello, Backup!").unwrap();
+        std::fs::write(&file, "Hello, Backup!").unwrap();
+        backup_file(&backup_dir, &file);
 
-        let restored_file = test_dir.path().join("file.txt");
+        std::fs::write(&file, "Uncommitted edit").unwrap();
 
-        let pre_restore_content = std::fs::read_to_string(&restored_file).unwrap();
+        restore_file(&backup_dir, &file, false, None).unwrap();
 
-        assert_eq!(pre_restore_content, "Hello, World!");
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "Hello, Backup!");
+    }
 
-        restore_file(&backup_dir, &restored_file, false).unwrap();
+    #[test]
+    fn test_restore_file_by_generation() {
+        let test_dir = tempfile::tempdir().expect("Failed to create temporary directory");
 
-        assert!(restored_file.exists());
+        let backup_dir = test_dir.path().join("backup");
+        std::fs::create_dir(&backup_dir).unwrap();
+
+        let file = test_dir.path().join("file.txt");
+        std::fs::write(&file, "Hello, World!").unwrap();
+        backup_file(&backup_dir, &file);
+        let first_generation = list_generations(&backup_dir, &file)[0];
+
+        std::fs::write(&file, "Hello, Backup!").unwrap();
+        backup_file(&backup_dir, &file);
 
-        let restored_content = std::fs::read_to_string(&restored_file).unwrap();
+        restore_file(&backup_dir, &file, false, Some(first_generation)).unwrap();
 
-        assert_eq!(restored_content, "Hello, Backup!");
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "Hello, World!");
     }
 }
@@ -0,0 +1,80 @@
+use std::{
+    ffi::OsStr,
+    process::{Command, Stdio},
+};
+
+/// A mechanism for running commands with elevated privileges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivilegeEscalation {
+    Sudo,
+}
+
+impl PrivilegeEscalation {
+    fn program(&self) -> &'static str {
+        match self {
+            PrivilegeEscalation::Sudo => "sudo",
+        }
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("which")
+            .arg(self.program())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+
+    /// Builds a `Command` that runs `program` through this escalation
+    /// mechanism.
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Command {
+        let mut command = Command::new(self.program());
+        command.arg(program);
+        command
+    }
+}
+
+/// The escalation mechanisms worth trying on the current OS, in preference
+/// order. `doas` or a polkit-style helper can be added here as they're
+/// supported.
+fn candidates() -> &'static [PrivilegeEscalation] {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    {
+        &[PrivilegeEscalation::Sudo]
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        &[]
+    }
+}
+
+/// Picks the first available escalation mechanism for the current OS, or an
+/// actionable error describing why none could be used.
+pub fn detect() -> std::io::Result<PrivilegeEscalation> {
+    let candidates = candidates();
+
+    if candidates.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            format!(
+                "root fixtures aren't supported on {}: no privilege escalation mechanism is known for this OS",
+                std::env::consts::OS
+            ),
+        ));
+    }
+
+    candidates
+        .iter()
+        .find(|escalation| escalation.is_available())
+        .copied()
+        .ok_or_else(|| {
+            let programs: Vec<&str> = candidates.iter().map(|c| c.program()).collect();
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "root fixtures require one of [{}], but none were found on PATH",
+                    programs.join(", ")
+                ),
+            )
+        })
+}